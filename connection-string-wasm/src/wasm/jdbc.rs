@@ -1,5 +1,6 @@
-use connection_string::JdbcString as BaseJdbcString;
+use connection_string::{ConnectionInfo, JdbcString as BaseJdbcString};
 use js_sys::Array;
+use std::convert::TryFrom;
 use wasm_bindgen::prelude::*;
 
 #[wasm_bindgen]
@@ -68,4 +69,12 @@ impl JdbcString {
     pub fn to_string(&self) -> String {
         format!("{}", self.inner)
     }
+
+    /// Convert this connection string to an equivalent ADO.net connection string.
+    pub fn to_ado_net(&self) -> Result<String, JsValue> {
+        let info = ConnectionInfo::try_from(&self.inner)
+            .map_err(|err| JsValue::from_str(&format!("{}", err)))?;
+
+        Ok(format!("{}", info.to_ado_net()))
+    }
 }
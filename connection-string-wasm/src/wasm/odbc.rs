@@ -0,0 +1,38 @@
+use wasm_bindgen::prelude::*;
+
+#[wasm_bindgen]
+#[derive(Debug)]
+/// A version of `OdbcString` to be used from web-assembly.
+pub struct OdbcString {
+    inner: connection_string::OdbcString,
+}
+
+#[wasm_bindgen]
+impl OdbcString {
+    #[wasm_bindgen(constructor)]
+    /// A constructor to create a new `OdbcString`, used from JavaScript with
+    /// `new OdbcString("Driver={ODBC Driver 18 for SQL Server};Server=tcp:host,1433")`.
+    pub fn new(s: &str) -> Result<OdbcString, JsValue> {
+        let inner = s
+            .parse()
+            .map_err(|err| JsValue::from_str(&format!("{}", err)))?;
+
+        Ok(Self { inner })
+    }
+
+    /// Get a parameter from the connection's key-value pairs
+    pub fn get(&self, key: &str) -> Option<String> {
+        self.inner.get(key).map(|s| s.to_string())
+    }
+
+    /// Set a parameter value to the connection's key-value pairs. If replacing
+    /// a pre-existing value, returns the old value.
+    pub fn set(&mut self, key: &str, value: &str) -> Option<String> {
+        self.inner.insert(key.into(), value.into())
+    }
+
+    /// Get a string representation of the `OdbcString`.
+    pub fn to_string(&self) -> String {
+        format!("{}", self.inner)
+    }
+}
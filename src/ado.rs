@@ -1,8 +1,8 @@
+use std::fmt;
 use std::ops::{Deref, DerefMut};
 use std::str::FromStr;
-use std::{collections::HashMap, fmt};
 
-use crate::{bail, ensure};
+use crate::{bail, ensure, ErrorKind, PropertyMap};
 
 /// An ADO.net connection string.
 ///
@@ -17,11 +17,11 @@ use crate::{bail, ensure};
 /// [Read more](https://docs.microsoft.com/en-us/dotnet/framework/data/adonet/connection-string-syntax)
 #[derive(Debug)]
 pub struct AdoNetString {
-    pairs: HashMap<String, String>,
+    pairs: PropertyMap,
 }
 
 impl Deref for AdoNetString {
-    type Target = HashMap<String, String>;
+    type Target = PropertyMap;
 
     fn deref(&self) -> &Self::Target {
         &self.pairs
@@ -34,6 +34,14 @@ impl DerefMut for AdoNetString {
     }
 }
 
+impl AdoNetString {
+    /// Build an `AdoNetString` from its key-value pairs. Used by the
+    /// cross-dialect conversion code in [`crate::ConnectionInfo`].
+    pub(crate) fn from_pairs(pairs: PropertyMap) -> Self {
+        Self { pairs }
+    }
+}
+
 // NOTE(yosh): Unfortunately we can't parse using `split(';')` because JDBC
 // strings support escaping. This means that `{;}` is valid and we need to write
 // an actual LR parser.
@@ -42,7 +50,7 @@ impl FromStr for AdoNetString {
 
     fn from_str(input: &str) -> Result<Self, Self::Err> {
         let mut lexer = Lexer::tokenize(input)?;
-        let mut pairs = HashMap::new();
+        let mut pairs = PropertyMap::new();
 
         // Iterate over `key=value` pairs.
         for n in 0.. {
@@ -56,7 +64,8 @@ impl FromStr for AdoNetString {
             //                   ^
             if n != 0 {
                 let err = "Key-value pairs must be separated by a `;`";
-                ensure!(lexer.next().kind() == &TokenKind::Semi, err);
+                let token = lexer.next();
+                ensure!(token.kind() == &TokenKind::Semi, ErrorKind::MissingDelimiter, err, token.loc);
 
                 // [property=value[;property=value][;]]
                 //                                  ^
@@ -67,13 +76,15 @@ impl FromStr for AdoNetString {
 
             // [property=[value][;property=value][;]]
             //  ^^^^^^^^
+            let key_loc = lexer.peek().loc;
             let key = read_ident(&mut lexer)?;
-            ensure!(!key.is_empty(), "Key must not be empty");
+            ensure!(!key.is_empty(), ErrorKind::InvalidPropertyKey, "Key must not be empty", key_loc);
 
             // [property=[value][;property=value][;]]
             //          ^
             let err = "key-value pairs must be joined by a `=`";
-            ensure!(lexer.next().kind() == &TokenKind::Eq, err);
+            let token = lexer.next();
+            ensure!(token.kind() == &TokenKind::Eq, ErrorKind::MissingEquals, err, token.loc);
 
             // [property=[value][;property=value][;]]
             //           ^^^^^
@@ -93,7 +104,7 @@ impl fmt::Display for AdoNetString {
             let mut output = String::with_capacity(s.len());
             let mut escaping = false;
             for b in s.chars() {
-                if matches!(b, ':' | '=' | '\\' | '/' | ';' | '{' | '}' | '[' | ']') {
+                if matches!(b, '=' | '\\' | '/' | ';' | '{' | '}' | '[' | ']') {
                     if !escaping {
                         escaping = true;
                         output.push('{');
@@ -164,7 +175,6 @@ fn read_ident(lexer: &mut Lexer) -> crate::Result<String> {
 #[derive(Debug, Clone)]
 struct Token {
     kind: TokenKind,
-    #[allow(dead_code)] // for future use...
     loc: Location,
 }
 
@@ -208,7 +218,7 @@ impl Lexer {
                     let mut buf = Vec::new();
                     loop {
                         match chars.next() {
-                            None => bail!("unclosed double quote"),
+                            None => bail!(ErrorKind::UnclosedEscapeLiteral, "unclosed double quote", @loc),
                             // When we read a double quote inside a double quote
                             // we need to lookahead to determine whether it's an
                             // escape sequence or a closing delimiter.
@@ -224,7 +234,7 @@ impl Lexer {
                                 Some(_) | None => break,
                             },
                             Some(c) if c.is_ascii() => buf.push(c),
-                            _ => bail!("Invalid ado.net token"),
+                            _ => bail!(ErrorKind::InvalidToken, "Invalid ado.net token", @loc),
                         }
                     }
                     TokenKind::Escaped(buf)
@@ -233,7 +243,7 @@ impl Lexer {
                     let mut buf = Vec::new();
                     loop {
                         match chars.next() {
-                            None => bail!("unclosed single quote"),
+                            None => bail!(ErrorKind::UnclosedEscapeLiteral, "unclosed single quote", @loc),
                             // When we read a single quote inside a single quote
                             // we need to lookahead to determine whether it's an
                             // escape sequence or a closing delimiter.
@@ -249,7 +259,7 @@ impl Lexer {
                                 Some(_) | None => break,
                             },
                             Some(c) if c.is_ascii() => buf.push(c),
-                            Some(c) => bail!("Invalid ado.net token `{}`", c),
+                            Some(c) => bail!(ErrorKind::InvalidToken, "Invalid ado.net token `{}`", c),
                         }
                     }
                     TokenKind::Escaped(buf)
@@ -259,10 +269,10 @@ impl Lexer {
                     // Read alphanumeric ASCII including whitespace until we find a closing curly.
                     loop {
                         match chars.next() {
-                            None => bail!("unclosed escape literal"),
+                            None => bail!(ErrorKind::UnclosedEscapeLiteral, "unclosed escape literal", @loc),
                             Some('}') => break,
                             Some(c) if c.is_ascii() => buf.push(c),
-                            Some(c) => bail!("Invalid ado.net token `{}`", c),
+                            Some(c) => bail!(ErrorKind::InvalidToken, "Invalid ado.net token `{}`", c),
                         }
                     }
                     TokenKind::Escaped(buf)
@@ -272,7 +282,7 @@ impl Lexer {
                 '\n' => TokenKind::Newline,
                 ' ' => TokenKind::Whitespace,
                 char if char.is_ascii() => TokenKind::Atom(char),
-                char => bail!("Invalid character found: {}", char),
+                char => bail!(ErrorKind::InvalidToken, "Invalid character found: {}", char),
             };
             tokens.push(Token::new(kind, loc));
             input = chars.as_str();
@@ -329,6 +339,15 @@ mod test {
         assert_eq!(ado.get(&key.to_lowercase()), Some(&value.to_owned()));
     }
 
+    #[test]
+    fn missing_equals_reports_kind_and_column() {
+        let err = "Server=tcp:localhost;Trusted;Other=x"
+            .parse::<AdoNetString>()
+            .unwrap_err();
+        assert_eq!(err.kind(), crate::ErrorKind::MissingEquals);
+        assert_eq!(err.column(), Some(28));
+    }
+
     // Source: https://docs.microsoft.com/en-us/dotnet/framework/data/adonet/connection-string-syntax#windows-authentication-with-sqlclient
     // https://docs.microsoft.com/en-us/dotnet/framework/data/adonet/connection-string-syntax#windows-authentication-with-sqlclient
     #[test]
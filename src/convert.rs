@@ -0,0 +1,267 @@
+use std::convert::TryFrom;
+
+use crate::{AdoNetString, ErrorKind, JdbcString, PropertyMap};
+
+/// A dialect-neutral connection string, shaped like the `scheme://` URLs most
+/// drivers ultimately derive their connection strings from: a server, a port,
+/// a database, credentials, and a bag of extra properties.
+///
+/// Convert a [`JdbcString`] or [`AdoNetString`] into a `ConnectionInfo` with
+/// [`TryFrom`], then call [`ConnectionInfo::to_jdbc`] or
+/// [`ConnectionInfo::to_ado_net`] to emit the other dialect. Well-known
+/// properties (server/host, database/databaseName, user id/user/uid,
+/// password/pwd, encrypt, trustServerCertificate) are mapped across dialects;
+/// anything else is preserved verbatim.
+///
+/// ```
+/// use connection_string::{AdoNetString, ConnectionInfo};
+/// use std::convert::TryFrom;
+///
+/// let ado: AdoNetString = "server=tcp:localhost,1433;database=foo".parse().unwrap();
+/// let info = ConnectionInfo::try_from(&ado).unwrap();
+/// let jdbc = info.to_jdbc();
+/// assert_eq!(format!("{}", jdbc), "jdbc:sqlserver://localhost:1433;databasename=foo");
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ConnectionInfo {
+    server: Option<String>,
+    port: Option<u16>,
+    database: Option<String>,
+    user: Option<String>,
+    password: Option<String>,
+    encrypt: Option<String>,
+    trust_server_certificate: Option<String>,
+    properties: PropertyMap,
+}
+
+impl ConnectionInfo {
+    /// Access the server or host name.
+    pub fn server(&self) -> Option<&str> {
+        self.server.as_deref()
+    }
+
+    /// Access the port number.
+    pub fn port(&self) -> Option<u16> {
+        self.port
+    }
+
+    /// Access the database name.
+    pub fn database(&self) -> Option<&str> {
+        self.database.as_deref()
+    }
+
+    /// Access the user name.
+    pub fn user(&self) -> Option<&str> {
+        self.user.as_deref()
+    }
+
+    /// Access the password.
+    pub fn password(&self) -> Option<&str> {
+        self.password.as_deref()
+    }
+
+    /// Access any properties that aren't part of the well-known set above.
+    pub fn properties(&self) -> &PropertyMap {
+        &self.properties
+    }
+
+    /// Emit an equivalent `jdbc:sqlserver` connection string.
+    pub fn to_jdbc(&self) -> JdbcString {
+        let mut properties = self.properties.clone();
+        if let Some(user) = &self.user {
+            properties.insert("user".into(), user.clone());
+        }
+        if let Some(password) = &self.password {
+            properties.insert("password".into(), password.clone());
+        }
+        if let Some(encrypt) = &self.encrypt {
+            properties.insert("encrypt".into(), encrypt.clone());
+        }
+        if let Some(trust) = &self.trust_server_certificate {
+            properties.insert("trustservercertificate".into(), trust.clone());
+        }
+        if let Some(database) = &self.database {
+            properties.insert("databasename".into(), database.clone());
+        }
+
+        JdbcString::from_parts(
+            "jdbc:sqlserver".to_owned(),
+            self.server.clone(),
+            self.port,
+            None,
+            properties,
+        )
+    }
+
+    /// Emit an equivalent ADO.net connection string.
+    pub fn to_ado_net(&self) -> AdoNetString {
+        let mut pairs = self.properties.clone();
+        if let Some(server) = &self.server {
+            let server = match self.port {
+                Some(port) => format!("tcp:{},{}", server, port),
+                None => server.clone(),
+            };
+            pairs.insert("server".into(), server);
+        }
+        if let Some(database) = &self.database {
+            pairs.insert("database".into(), database.clone());
+        }
+        if let Some(user) = &self.user {
+            pairs.insert("user id".into(), user.clone());
+        }
+        if let Some(password) = &self.password {
+            pairs.insert("password".into(), password.clone());
+        }
+        if let Some(encrypt) = &self.encrypt {
+            pairs.insert("encrypt".into(), encrypt.clone());
+        }
+        if let Some(trust) = &self.trust_server_certificate {
+            pairs.insert("trustservercertificate".into(), trust.clone());
+        }
+
+        AdoNetString::from_pairs(pairs)
+    }
+}
+
+impl TryFrom<&JdbcString> for ConnectionInfo {
+    type Error = crate::Error;
+
+    fn try_from(jdbc: &JdbcString) -> crate::Result<Self> {
+        if jdbc.instance_name().is_some() {
+            return Err(crate::Error::new(
+                ErrorKind::UnsupportedConversion,
+                "a JDBC named instance has no lossless equivalent in other dialects",
+            ));
+        }
+
+        // `ConnectionInfo` only models a single value per key, so a key
+        // repeated in the source JDBC string collapses to its last value
+        // here, same as a plain `HashMap` would.
+        let mut properties: PropertyMap = jdbc
+            .properties()
+            .iter()
+            .map(|(k, v)| (k.to_owned(), v.to_owned()))
+            .collect();
+        let mut database = jdbc.database().map(|s| s.to_owned());
+        if database.is_none() {
+            database = take_alias(&mut properties, &["databasename", "database"]);
+        }
+
+        Ok(Self {
+            server: jdbc.server_name().map(|s| s.to_owned()),
+            port: jdbc.port(),
+            database,
+            user: take_alias(&mut properties, &["user", "user id", "uid"]),
+            password: take_alias(&mut properties, &["password", "pwd"]),
+            encrypt: take_alias(&mut properties, &["encrypt"]),
+            trust_server_certificate: take_alias(
+                &mut properties,
+                &["trustservercertificate", "trust server certificate"],
+            ),
+            properties,
+        })
+    }
+}
+
+impl TryFrom<&AdoNetString> for ConnectionInfo {
+    type Error = crate::Error;
+
+    fn try_from(ado: &AdoNetString) -> crate::Result<Self> {
+        let mut pairs: PropertyMap = ado
+            .iter()
+            .map(|(k, v)| (k.to_owned(), v.to_owned()))
+            .collect();
+
+        let (server, port) = match take_alias(&mut pairs, &["server", "data source"]) {
+            Some(server) => split_server_port(&server),
+            None => (None, None),
+        };
+
+        Ok(Self {
+            server,
+            port,
+            database: take_alias(&mut pairs, &["database", "initial catalog"]),
+            user: take_alias(&mut pairs, &["user id", "user", "uid"]),
+            password: take_alias(&mut pairs, &["password", "pwd"]),
+            encrypt: take_alias(&mut pairs, &["encrypt"]),
+            trust_server_certificate: take_alias(
+                &mut pairs,
+                &["trustservercertificate", "trust server certificate"],
+            ),
+            properties: pairs,
+        })
+    }
+}
+
+/// Remove and return the first of a list of aliased keys found in `properties`.
+fn take_alias(properties: &mut PropertyMap, aliases: &[&str]) -> Option<String> {
+    aliases.iter().find_map(|alias| properties.remove(alias))
+}
+
+/// Split an ADO.net `server` value like `tcp:host,1433` or `host,1433` into
+/// its host and port, falling back to treating the whole value as the host.
+fn split_server_port(server: &str) -> (Option<String>, Option<u16>) {
+    let stripped = server.strip_prefix("tcp:").unwrap_or(server);
+    match stripped.rsplit_once(',') {
+        Some((host, port)) if !host.is_empty() => match port.trim().parse() {
+            Ok(port) => (Some(host.to_owned()), Some(port)),
+            Err(_) => (Some(stripped.to_owned()), None),
+        },
+        _ => (Some(stripped.to_owned()), None),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::ConnectionInfo;
+    use crate::{AdoNetString, JdbcString};
+    use std::convert::TryFrom;
+
+    #[test]
+    fn ado_net_to_jdbc() {
+        let ado: AdoNetString = "server=tcp:localhost,1433;database=foo"
+            .parse()
+            .unwrap();
+        let info = ConnectionInfo::try_from(&ado).unwrap();
+        let jdbc = info.to_jdbc();
+
+        assert_eq!(jdbc.server_name(), Some("localhost"));
+        assert_eq!(jdbc.port(), Some(1433));
+        assert_eq!(jdbc.properties().get("databasename"), Some(&"foo".to_owned()));
+    }
+
+    #[test]
+    fn jdbc_to_ado_net() {
+        let jdbc: JdbcString = "jdbc:sqlserver://localhost:1433;databaseName=foo;user=sa;password=hunter2"
+            .parse()
+            .unwrap();
+        let info = ConnectionInfo::try_from(&jdbc).unwrap();
+        let ado = info.to_ado_net();
+
+        assert_eq!(ado.get("server"), Some(&"tcp:localhost,1433".to_owned()));
+        assert_eq!(ado.get("database"), Some(&"foo".to_owned()));
+        assert_eq!(ado.get("user id"), Some(&"sa".to_owned()));
+        assert_eq!(ado.get("password"), Some(&"hunter2".to_owned()));
+
+        // `:` has no special meaning in the ADO.net grammar, so it must not
+        // come back wrapped in a `{...}` escape.
+        assert!(format!("{}", ado).contains("server=tcp:localhost,1433"));
+    }
+
+    #[test]
+    fn unknown_properties_survive_the_round_trip() {
+        let jdbc: JdbcString = "jdbc:sqlserver://localhost;applicationName=myapp"
+            .parse()
+            .unwrap();
+        let info = ConnectionInfo::try_from(&jdbc).unwrap();
+        let ado = info.to_ado_net();
+
+        assert_eq!(ado.get("applicationname"), Some(&"myapp".to_owned()));
+    }
+
+    #[test]
+    fn named_instance_is_not_losslessly_convertible() {
+        let jdbc: JdbcString = r#"jdbc:sqlserver://server\instance"#.parse().unwrap();
+        assert!(ConnectionInfo::try_from(&jdbc).is_err());
+    }
+}
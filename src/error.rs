@@ -1,31 +1,80 @@
 use std::fmt::{self, Display};
 
+/// The kind of error that occurred while parsing a connection string.
+///
+/// This lets callers match on failures programmatically instead of comparing
+/// against the human-readable message produced by [`Error`]'s `Display` impl.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum ErrorKind {
+    /// The `jdbc:<sub-protocol>` prefix was missing or malformed.
+    InvalidSubProtocol,
+    /// The server name could not be parsed.
+    InvalidServerName,
+    /// The instance name (following a `\`) could not be parsed.
+    InvalidInstanceName,
+    /// The port number was missing or not a valid `u16`.
+    InvalidPort,
+    /// The database name (following a `/`) could not be parsed.
+    InvalidDatabaseName,
+    /// A `{...}` escape literal was never closed.
+    UnclosedEscapeLiteral,
+    /// An unexpected or unrecognized token was encountered.
+    InvalidToken,
+    /// A property's key and value were not joined by a `=`.
+    MissingEquals,
+    /// Key-value pairs were not separated by the expected delimiter.
+    MissingDelimiter,
+    /// A property key could not be parsed.
+    InvalidPropertyKey,
+    /// A property value could not be parsed.
+    InvalidPropertyValue,
+    /// The connection string uses a feature that has no lossless equivalent
+    /// in the target dialect.
+    UnsupportedConversion,
+}
+
 /// A connection string error.
 #[derive(Debug)]
 pub struct Error {
+    kind: ErrorKind,
     msg: String,
+    column: Option<usize>,
 }
 
-/// Create a new Error.
 impl Error {
     /// Create a new instance of `Error`.
-    pub fn new(msg: &str) -> Self {
+    pub fn new(kind: ErrorKind, msg: &str) -> Self {
         Self {
+            kind,
             msg: msg.to_owned(),
+            column: None,
         }
     }
-}
 
-impl From<std::num::ParseIntError> for Error {
-    fn from(err: std::num::ParseIntError) -> Self {
+    /// Create a new instance of `Error`, capturing the column at which the
+    /// offending token was found.
+    pub fn with_column(kind: ErrorKind, msg: &str, column: usize) -> Self {
         Self {
-            msg: format!("{}", err),
+            kind,
+            msg: msg.to_owned(),
+            column: Some(column),
         }
     }
+
+    /// The kind of error that occurred.
+    pub fn kind(&self) -> ErrorKind {
+        self.kind
+    }
+
+    /// The column in the source string the error was found at, if known.
+    pub fn column(&self) -> Option<usize> {
+        self.column
+    }
 }
 
 impl Display for Error {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "{}", self.msg)
+        write!(f, "Conversion error: {}", self.msg)
     }
 }
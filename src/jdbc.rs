@@ -1,9 +1,9 @@
+use std::fmt::Display;
 use std::str::FromStr;
-use std::{collections::HashMap, fmt::Display};
 
-use crate::{bail, ensure};
+use crate::{bail, ensure, ErrorKind, PropertyMap};
 
-/// JDBC connection string parser for SqlServer
+/// JDBC connection string parser for SqlServer, PostgreSQL and MySQL.
 ///
 /// [Read more](https://docs.microsoft.com/en-us/sql/connect/jdbc/building-the-connection-url?view=sql-server-ver15)
 ///
@@ -11,6 +11,7 @@ use crate::{bail, ensure};
 ///
 /// ```txt
 /// jdbc:sqlserver://[serverName[\instanceName][:portNumber]][;property=value[;property=value]]
+/// jdbc:postgresql://[serverName[:portNumber]][/database][?property=value[&property=value]]
 /// ```
 #[derive(Debug, PartialEq, Eq, Clone)]
 pub struct JdbcString {
@@ -18,7 +19,9 @@ pub struct JdbcString {
     server_name: Option<String>,
     instance_name: Option<String>,
     port: Option<u16>,
-    properties: HashMap<String, String>,
+    database: Option<String>,
+    delimiter: Delimiter,
+    properties: PropertyMap,
 }
 
 impl JdbcString {
@@ -42,15 +45,55 @@ impl JdbcString {
         self.port
     }
 
+    /// Access the connection's database name, as found in the `/database` path
+    /// segment used by drivers like PostgreSQL and MySQL.
+    pub fn database(&self) -> Option<&str> {
+        self.database.as_deref()
+    }
+
     /// Access the connection's key-value pairs
-    pub fn properties(&self) -> &HashMap<String, String> {
+    pub fn properties(&self) -> &PropertyMap {
         &self.properties
     }
 
     /// Mutably access the connection's key-value pairs
-    pub fn properties_mut(&mut self) -> &mut HashMap<String, String> {
+    pub fn properties_mut(&mut self) -> &mut PropertyMap {
         &mut self.properties
     }
+
+    /// Build a `JdbcString` from its parts, picking the property delimiter
+    /// conventional for the given sub-protocol. Used by the cross-dialect
+    /// conversion code in [`crate::ConnectionInfo`].
+    pub(crate) fn from_parts(
+        sub_protocol: String,
+        server_name: Option<String>,
+        port: Option<u16>,
+        database: Option<String>,
+        properties: PropertyMap,
+    ) -> Self {
+        let delimiter = match sub_protocol.as_str() {
+            "jdbc:postgresql" | "jdbc:mysql" => Delimiter::QueryString,
+            _ => Delimiter::Semicolon,
+        };
+        Self {
+            sub_protocol,
+            server_name,
+            instance_name: None,
+            port,
+            database,
+            delimiter,
+            properties,
+        }
+    }
+}
+
+/// Which family of delimiters separates key-value pairs in the connection
+/// string: `;key=value;key2=value2` (SqlServer) or `?key=value&key2=value2`
+/// (PostgreSQL, MySQL).
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+enum Delimiter {
+    Semicolon,
+    QueryString,
 }
 
 impl Display for JdbcString {
@@ -60,7 +103,10 @@ impl Display for JdbcString {
             let mut output = String::with_capacity(s.len());
             let mut escaping = false;
             for b in s.chars() {
-                if matches!(b, ':' | '=' | '\\' | '/' | ';' | '{' | '}' | '[' | ']') {
+                if matches!(
+                    b,
+                    ':' | '=' | '\\' | '/' | ';' | '{' | '}' | '[' | ']' | '?' | '&'
+                ) {
                     if !escaping {
                         escaping = true;
                         output.push('{');
@@ -90,9 +136,17 @@ impl Display for JdbcString {
         if let Some(port) = self.port {
             write!(f, ":{}", port)?;
         }
+        if let Some(database) = &self.database {
+            write!(f, "/{}", escape(database))?;
+        }
 
-        for (k, v) in self.properties().iter() {
-            write!(f, ";{}={}", escape(k.trim()), escape(v.trim()))?;
+        for (i, (k, v)) in self.properties().iter().enumerate() {
+            let sep = match (self.delimiter, i) {
+                (Delimiter::Semicolon, _) => ';',
+                (Delimiter::QueryString, 0) => '?',
+                (Delimiter::QueryString, _) => '&',
+            };
+            write!(f, "{}{}={}", sep, escape(k.trim()), escape(v.trim()))?;
         }
         Ok(())
     }
@@ -113,12 +167,16 @@ impl FromStr for JdbcString {
         // ```
         let err = "Invalid JDBC sub-protocol";
         cmp_str(&mut lexer, "jdbc", err)?;
-        ensure!(lexer.next().kind() == &TokenKind::Colon, err);
-        let sub_protocol = format!("jdbc:{}", read_ident(&mut lexer, err)?);
+        let token = lexer.next();
+        ensure!(token.kind() == &TokenKind::Colon, ErrorKind::InvalidSubProtocol, err, token.loc);
+        let sub_protocol = format!("jdbc:{}", read_ident(&mut lexer, ErrorKind::InvalidSubProtocol, err)?);
 
-        ensure!(lexer.next().kind() == &TokenKind::Colon, err);
-        ensure!(lexer.next().kind() == &TokenKind::FSlash, err);
-        ensure!(lexer.next().kind() == &TokenKind::FSlash, err);
+        let token = lexer.next();
+        ensure!(token.kind() == &TokenKind::Colon, ErrorKind::InvalidSubProtocol, err, token.loc);
+        let token = lexer.next();
+        ensure!(token.kind() == &TokenKind::FSlash, ErrorKind::InvalidSubProtocol, err, token.loc);
+        let token = lexer.next();
+        ensure!(token.kind() == &TokenKind::FSlash, ErrorKind::InvalidSubProtocol, err, token.loc);
 
         // ```
         // jdbc:sqlserver://[serverName[\instanceName][:portNumber]][;property=value[;property=value]]
@@ -126,7 +184,12 @@ impl FromStr for JdbcString {
         // ```
         let mut server_name = None;
         if matches!(lexer.peek().kind(), TokenKind::Atom(_) | TokenKind::Escaped(_)) {
-            server_name = Some(read_ident(&mut lexer, "Invalid server name")?);
+            server_name = Some(read_ident_until(
+                &mut lexer,
+                ErrorKind::InvalidServerName,
+                "Invalid server name",
+                Some('?'),
+            )?);
         }
 
         // ```
@@ -136,7 +199,11 @@ impl FromStr for JdbcString {
         let mut instance_name = None;
         if matches!(lexer.peek().kind(), TokenKind::BSlash) {
             let _ = lexer.next();
-            instance_name = Some(read_ident(&mut lexer, "Invalid instance name")?);
+            instance_name = Some(read_ident(
+                &mut lexer,
+                ErrorKind::InvalidInstanceName,
+                "Invalid instance name",
+            )?);
         }
 
         // ```
@@ -146,46 +213,106 @@ impl FromStr for JdbcString {
         let mut port = None;
         if matches!(lexer.peek().kind(), TokenKind::Colon) {
             let _ = lexer.next();
+            let loc = lexer.peek().loc;
             let err = "Invalid port";
-            let s = read_ident(&mut lexer, err)?;
-            port = Some(s.parse()?);
+            let s = read_ident_until(&mut lexer, ErrorKind::InvalidPort, err, Some('?'))?;
+            port = Some(s.parse().map_err(|_| {
+                crate::Error::with_column(ErrorKind::InvalidPort, err, loc.column)
+            })?);
         }
 
+        // ```
+        // jdbc:postgresql://[serverName[:portNumber]][/database][?property=value[&property=value]]
+        //                                             ^^^^^^^^^^
+        // ```
+        let mut database = None;
+        if matches!(lexer.peek().kind(), TokenKind::FSlash) {
+            let _ = lexer.next();
+            // An unescaped `?` ends the database name and starts the query
+            // string.
+            database = Some(read_ident_until(
+                &mut lexer,
+                ErrorKind::InvalidDatabaseName,
+                "Invalid database name",
+                Some('?'),
+            )?);
+        }
+
+        // Postgres/MySQL-style drivers separate properties with `?`/`&` instead
+        // of `;`. Prefer whichever delimiter is actually present, falling back
+        // to the sub-protocol's conventional delimiter when there are no
+        // properties to disambiguate from.
+        let delimiter = match lexer.peek().kind() {
+            TokenKind::Semi => Delimiter::Semicolon,
+            TokenKind::Atom('?') => Delimiter::QueryString,
+            _ => match sub_protocol.as_str() {
+                "jdbc:postgresql" | "jdbc:mysql" => Delimiter::QueryString,
+                _ => Delimiter::Semicolon,
+            },
+        };
+
         // ```
         // jdbc:sqlserver://[serverName[\instanceName][:portNumber]][;property=value[;property=value]]
         //                                                          ^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^
+        // jdbc:postgresql://[serverName[:portNumber]][/database][?property=value[&property=value]]
+        //                                                        ^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^
         // ```
-        // NOTE: we're choosing to only keep the last value per key rather than support multiple inserts per key.
-        let mut properties = HashMap::new();
-        while let TokenKind::Semi = lexer.peek().kind() {
+        let mut properties = PropertyMap::new();
+        let mut first_property = true;
+        loop {
+            let is_separator = match (delimiter, first_property) {
+                (Delimiter::Semicolon, _) => lexer.peek().kind() == &TokenKind::Semi,
+                (Delimiter::QueryString, true) => matches!(lexer.peek().kind(), TokenKind::Atom('?')),
+                (Delimiter::QueryString, false) => matches!(lexer.peek().kind(), TokenKind::Atom('&')),
+            };
+            if !is_separator {
+                break;
+            }
             let _ = lexer.next();
+            first_property = false;
 
-            // Handle trailing semis.
+            // Handle trailing delimiters.
             if let TokenKind::Eof = lexer.peek().kind() {
                 let _ = lexer.next();
                 break;
             }
 
+            // Once the delimiter is a query string, an unescaped `&` ends the
+            // current key/value instead of being ordinary content.
+            let stop = match delimiter {
+                Delimiter::QueryString => Some('&'),
+                Delimiter::Semicolon => None,
+            };
+
             let err = "Invalid property key";
-            let key = read_ident(&mut lexer, err)?.to_lowercase();
+            let key =
+                read_ident_until(&mut lexer, ErrorKind::InvalidPropertyKey, err, stop)?.to_lowercase();
 
             let err = "Property pairs must be joined by a `=`";
-            ensure!(lexer.next().kind() == &TokenKind::Eq, err);
+            let token = lexer.next();
+            ensure!(token.kind() == &TokenKind::Eq, ErrorKind::MissingEquals, err, token.loc);
 
             let err = "Invalid property value";
-            let value = read_ident(&mut lexer, err)?;
+            let value = read_ident_until(&mut lexer, ErrorKind::InvalidPropertyValue, err, stop)?;
 
-            properties.insert(key, value);
+            properties.append(key, value);
         }
 
         let token = lexer.next();
-        ensure!(token.kind() == &TokenKind::Eof, "Invalid JDBC token");
+        ensure!(
+            token.kind() == &TokenKind::Eof,
+            ErrorKind::InvalidToken,
+            "Invalid JDBC token",
+            token.loc
+        );
 
         Ok(Self {
             sub_protocol,
             server_name,
             instance_name,
             port,
+            database,
+            delimiter,
             properties,
         })
     }
@@ -194,27 +321,42 @@ impl FromStr for JdbcString {
 /// Validate a sequence of `TokenKind::Atom` matches the content of a string.
 fn cmp_str(lexer: &mut Lexer, s: &str, err_msg: &'static str) -> crate::Result<()> {
     for char in s.chars() {
+        let token = lexer.next();
         if let Token {
             kind: TokenKind::Atom(tchar),
             ..
-        } = lexer.next()
+        } = token
         {
-            ensure!(char == tchar, err_msg);
+            ensure!(char == tchar, ErrorKind::InvalidSubProtocol, err_msg, token.loc);
         } else {
-            bail!(err_msg);
+            bail!(ErrorKind::InvalidSubProtocol, err_msg, @token.loc);
         }
     }
     Ok(())
 }
 
 /// Read sequences of `TokenKind::Atom` and `TokenKind::Escaped` into a String.
-fn read_ident(lexer: &mut Lexer, err_msg: &'static str) -> crate::Result<String> {
+fn read_ident(lexer: &mut Lexer, kind: ErrorKind, err_msg: &'static str) -> crate::Result<String> {
+    read_ident_until(lexer, kind, err_msg, None)
+}
+
+/// Like [`read_ident`], but also stops (without consuming) at an unescaped
+/// `stop` atom. Used where an unescaped `?` (start of the query string) or
+/// `&` (next `?key=value&...` property) ends the current part; in a
+/// `;`-delimited property key or value both are ordinary content.
+fn read_ident_until(
+    lexer: &mut Lexer,
+    kind: ErrorKind,
+    err_msg: &'static str,
+    stop: Option<char>,
+) -> crate::Result<String> {
+    let start = lexer.peek().loc;
     let mut output = String::new();
     loop {
         let token = lexer.next();
         match token.kind() {
             TokenKind::Escaped(seq) => output.extend(seq),
-            TokenKind::Atom(c) => output.push(*c),
+            TokenKind::Atom(c) if Some(*c) != stop => output.push(*c),
             _ => {
                 // push the token back in the lexer
                 lexer.push(token);
@@ -223,7 +365,7 @@ fn read_ident(lexer: &mut Lexer, err_msg: &'static str) -> crate::Result<String>
         }
     }
     match output.len() {
-        0 => bail!(err_msg),
+        0 => bail!(kind, err_msg, @start),
         _ => Ok(output),
     }
 }
@@ -253,16 +395,16 @@ impl Lexer {
                     // Read alphanumeric ASCII including whitespace until we find a closing curly.
                     loop {
                         match chars.next() {
-                            None => bail!("unclosed escape literal"),
+                            None => bail!(ErrorKind::UnclosedEscapeLiteral, "unclosed escape literal", @loc),
                             Some('}') => break,
                             Some(c) if c.is_ascii() => buf.push(c),
-                            Some(c) => bail!("Invalid JDBC token `{}`", c),
+                            Some(c) => bail!(ErrorKind::InvalidToken, "Invalid JDBC token `{}`", c),
                         }
                     }
                     TokenKind::Escaped(buf)
                 }
                 c if c.is_ascii() => TokenKind::Atom(c),
-                c => bail!("Invalid JDBC token `{}`", c),
+                c => bail!(ErrorKind::InvalidToken, "Invalid JDBC token `{}`", c),
             };
             tokens.push(Token { kind, loc });
             input = chars.as_str();
@@ -416,6 +558,13 @@ mod test {
         Ok(())
     }
 
+    #[test]
+    fn invalid_port_reports_kind_and_column() {
+        let err = "jdbc:sqlserver://h:zz".parse::<JdbcString>().unwrap_err();
+        assert_eq!(err.kind(), crate::ErrorKind::InvalidPort);
+        assert_eq!(err.column(), Some(19));
+    }
+
     #[test]
     fn whitespace() -> crate::Result<()> {
         let conn: JdbcString =
@@ -472,17 +621,67 @@ mod test {
         let input = r#"jdbc:sqlserver://test-db-mssql-2017:1433;user=SA;encrypt=DANGER_PLAINTEXT;isolationlevel=READ UNCOMMITTED;schema=NonEmbeddedUpsertDesignSpec;trustservercertificate=true;password=<YourStrong@Passw0rd>"#;
         let conn: JdbcString = input.parse()?;
 
-        let output = format!("{}", conn);
-        let mut output: Vec<String> = output.split(';').map(|s| s.to_owned()).collect();
-        output.pop();
-        output.sort();
+        // Properties now preserve insertion order, so this round-trips
+        // exactly instead of requiring both sides to be sorted first.
+        assert_eq!(format!("{}", conn), input);
+        Ok(())
+    }
 
-        let input = format!("{}", conn);
-        let mut input: Vec<String> = input.split(';').map(|s| s.to_owned()).collect();
-        input.pop();
-        input.sort();
+    #[test]
+    fn repeated_keys_preserve_all_values_and_order() -> crate::Result<()> {
+        let input = "jdbc:sqlserver://server;foo=bar;foo=baz";
+        let conn: JdbcString = input.parse()?;
 
-        assert_eq!(output, input);
+        let props = conn.properties();
+        assert_eq!(props.get("foo"), Some(&"baz".to_owned()));
+        assert_eq!(
+            props.get_all("foo").collect::<Vec<_>>(),
+            vec![&"bar".to_owned(), &"baz".to_owned()]
+        );
+        assert_eq!(format!("{}", conn), input);
+        Ok(())
+    }
+
+    // `?`/`&` are only delimiters for the postgres/mysql query-string style;
+    // elsewhere (e.g. a `;`-delimited SqlServer value) they're ordinary content.
+    #[test]
+    fn semicolon_delimited_value_may_contain_raw_question_and_amp() -> crate::Result<()> {
+        let conn: JdbcString = "jdbc:sqlserver://server;key=a?b".parse()?;
+        assert_eq!(conn.properties().get("key"), Some(&"a?b".to_owned()));
+
+        let conn: JdbcString = "jdbc:sqlserver://server;key=a&b".parse()?;
+        assert_eq!(conn.properties().get("key"), Some(&"a&b".to_owned()));
+        Ok(())
+    }
+
+    #[test]
+    fn parse_postgres_database_and_query_params() -> crate::Result<()> {
+        let conn: JdbcString =
+            "jdbc:postgresql://localhost:5432/mydb?user=postgres&password=secret".parse()?;
+        assert_eq!(conn.sub_protocol(), "jdbc:postgresql");
+        assert_eq!(conn.server_name(), Some("localhost"));
+        assert_eq!(conn.port(), Some(5432));
+        assert_eq!(conn.database(), Some("mydb"));
+
+        let props = conn.properties();
+        assert_eq!(props.get("user"), Some(&"postgres".to_string()));
+        assert_eq!(props.get("password"), Some(&"secret".to_string()));
+        Ok(())
+    }
+
+    #[test]
+    fn parse_mysql_database_without_query_params() -> crate::Result<()> {
+        let conn: JdbcString = "jdbc:mysql://localhost:3306/mydb".parse()?;
+        assert_eq!(conn.sub_protocol(), "jdbc:mysql");
+        assert_eq!(conn.database(), Some("mydb"));
+        Ok(())
+    }
+
+    #[test]
+    fn display_postgres_round_trip() -> crate::Result<()> {
+        let input = "jdbc:postgresql://localhost:5432/mydb?user=postgres";
+        let conn: JdbcString = input.parse()?;
+        assert_eq!(format!("{}", conn), input);
         Ok(())
     }
 }
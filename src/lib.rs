@@ -17,20 +17,40 @@
 //! let input = "Persist Security Info=False;Integrated Security=true;\nInitial Catalog=AdventureWorks;Server=MSSQL1";
 //! let _: AdoNetString = input.parse().unwrap();
 //! ```
+//!
+//! ODBC
+//! ```
+//! use connection_string::OdbcString;
+//!
+//! let input = "Driver={ODBC Driver 18 for SQL Server};Server=tcp:host,1433;Uid=sa;Pwd=secret";
+//! let _: OdbcString = input.parse().unwrap();
+//! ```
 
 #![forbid(unsafe_code, rust_2018_idioms)]
 #![deny(missing_debug_implementations, nonstandard_style)]
 #![warn(missing_docs, future_incompatible, unreachable_pub)]
 
 mod ado;
+mod convert;
 mod error;
 mod jdbc;
+mod odbc;
+mod properties;
+
+#[cfg(feature = "serde")]
+mod serde_impl;
 
 #[macro_use]
 mod utils;
 
 pub use ado::AdoNetString;
+pub use convert::ConnectionInfo;
 pub use jdbc::JdbcString;
+pub use odbc::OdbcString;
+pub use properties::PropertyMap;
+
+#[cfg(feature = "serde")]
+pub use serde_impl::serde_as_string;
 
-pub use error::Error;
+pub use error::{Error, ErrorKind};
 type Result<T> = std::result::Result<T, Error>;
@@ -0,0 +1,296 @@
+use std::fmt;
+use std::ops::{Deref, DerefMut};
+use std::str::FromStr;
+
+use crate::{bail, ensure, ErrorKind, PropertyMap};
+
+/// An ODBC / DSN connection string.
+///
+/// Keywords are not case-sensitive. A value wrapped in `{...}` is a literal
+/// that may contain `;`, `=` and `}` (escaped by doubling it, `}}`).
+///
+/// [Read more](https://docs.microsoft.com/en-us/sql/odbc/reference/syntax/sqldriverconnect-function?view=sql-server-ver15#comments)
+#[derive(Debug)]
+pub struct OdbcString {
+    pairs: PropertyMap,
+}
+
+impl Deref for OdbcString {
+    type Target = PropertyMap;
+
+    fn deref(&self) -> &Self::Target {
+        &self.pairs
+    }
+}
+
+impl DerefMut for OdbcString {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.pairs
+    }
+}
+
+// NOTE: Unfortunately we can't parse using `split(';')` because ODBC
+// strings support escaping. This means that `{;}` is valid and we need to write
+// an actual LR parser.
+impl FromStr for OdbcString {
+    type Err = crate::Error;
+
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        let mut lexer = Lexer::tokenize(input)?;
+        let mut pairs = PropertyMap::new();
+
+        // Iterate over `Keyword=Value` pairs.
+        for n in 0.. {
+            // [Keyword=[Value][;Keyword=Value][;]]
+            //                                     ^
+            if lexer.peek().kind() == &TokenKind::Eof {
+                break;
+            }
+
+            // [Keyword=[Value][;Keyword=Value][;]]
+            //                 ^
+            if n != 0 {
+                let err = "Key-value pairs must be separated by a `;`";
+                let token = lexer.next();
+                ensure!(token.kind() == &TokenKind::Semi, ErrorKind::MissingDelimiter, err, token.loc);
+
+                // [Keyword=Value[;Keyword=Value][;]]
+                //                                ^
+                if lexer.peek().kind() == &TokenKind::Eof {
+                    break;
+                }
+            }
+
+            // [Keyword=[Value][;Keyword=Value][;]]
+            //  ^^^^^^^
+            let key_loc = lexer.peek().loc;
+            let key = read_ident(&mut lexer)?;
+            ensure!(!key.is_empty(), ErrorKind::InvalidPropertyKey, "Key must not be empty", key_loc);
+
+            // [Keyword=[Value][;Keyword=Value][;]]
+            //         ^
+            let err = "Key-value pairs must be joined by a `=`";
+            let token = lexer.next();
+            ensure!(token.kind() == &TokenKind::Eq, ErrorKind::MissingEquals, err, token.loc);
+
+            // [Keyword=[Value][;Keyword=Value][;]]
+            //          ^^^^^
+            let value = read_ident(&mut lexer)?;
+
+            let key = key.to_lowercase();
+            pairs.insert(key, value);
+        }
+        Ok(Self { pairs })
+    }
+}
+
+impl fmt::Display for OdbcString {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        /// Wrap a value in `{...}` and double any `}` if it contains a
+        /// character that's significant to the ODBC grammar.
+        fn escape(s: &str) -> String {
+            if !s.chars().any(|c| matches!(c, ';' | '=' | '{' | '}')) {
+                return s.to_owned();
+            }
+            let mut output = String::with_capacity(s.len() + 2);
+            output.push('{');
+            for c in s.chars() {
+                if c == '}' {
+                    output.push('}');
+                }
+                output.push(c);
+            }
+            output.push('}');
+            output
+        }
+
+        let total_pairs = self.pairs.len();
+
+        for (i, (k, v)) in self.pairs.iter().enumerate() {
+            write!(f, "{}={}", escape(k), escape(v))?;
+
+            if i < total_pairs - 1 {
+                write!(f, ";")?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Read either a valid key or value from the lexer.
+fn read_ident(lexer: &mut Lexer) -> crate::Result<String> {
+    let mut output = String::new();
+    loop {
+        let Token { kind, .. } = lexer.peek();
+        match kind {
+            TokenKind::Atom(c) => {
+                let _ = lexer.next();
+                output.push(c);
+            }
+            TokenKind::Escaped(seq) => {
+                let _ = lexer.next();
+                output.extend(seq);
+            }
+            TokenKind::Semi | TokenKind::Eq | TokenKind::Eof => break,
+        }
+    }
+    Ok(output)
+}
+
+#[derive(Debug, Clone)]
+struct Token {
+    kind: TokenKind,
+    loc: Location,
+}
+
+impl Token {
+    /// Create a new instance.
+    fn new(kind: TokenKind, loc: Location) -> Self {
+        Self { kind, loc }
+    }
+
+    fn kind(&self) -> &TokenKind {
+        &self.kind
+    }
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+enum TokenKind {
+    Semi,
+    Eq,
+    Atom(char),
+    Escaped(Vec<char>),
+    Eof,
+}
+
+#[derive(Debug)]
+struct Lexer {
+    tokens: Vec<Token>,
+}
+
+impl Lexer {
+    /// Parse a string into a sequence of tokens.
+    fn tokenize(mut input: &str) -> crate::Result<Self> {
+        let mut tokens = vec![];
+        let mut loc = Location::default();
+        while !input.is_empty() {
+            let old_input = input;
+            let mut chars = input.chars();
+            let kind = match chars.next().unwrap() {
+                '{' => {
+                    let mut buf = Vec::new();
+                    // Read until the closing curly, unescaping any doubled `}}`.
+                    loop {
+                        match chars.next() {
+                            None => bail!(ErrorKind::UnclosedEscapeLiteral, "unclosed escape literal", @loc),
+                            Some('}') => match lookahead(&chars) {
+                                Some('}') => {
+                                    let _ = chars.next();
+                                    buf.push('}');
+                                }
+                                Some(_) | None => break,
+                            },
+                            Some(c) if c.is_ascii() => buf.push(c),
+                            Some(c) => bail!(ErrorKind::InvalidToken, "Invalid ODBC token `{}`", c),
+                        }
+                    }
+                    TokenKind::Escaped(buf)
+                }
+                ';' => TokenKind::Semi,
+                '=' => TokenKind::Eq,
+                char if char.is_ascii() => TokenKind::Atom(char),
+                char => bail!(ErrorKind::InvalidToken, "Invalid character found: {}", char),
+            };
+            tokens.push(Token::new(kind, loc));
+            input = chars.as_str();
+
+            let consumed = old_input.len() - input.len();
+            loc.advance(&old_input[..consumed]);
+        }
+        tokens.reverse();
+        Ok(Self { tokens })
+    }
+
+    /// Get the next token from the queue.
+    #[must_use]
+    pub(crate) fn next(&mut self) -> Token {
+        self.tokens.pop().unwrap_or(Token {
+            kind: TokenKind::Eof,
+            loc: Location::default(),
+        })
+    }
+
+    /// Peek at the next token in the queue.
+    #[must_use]
+    pub(crate) fn peek(&mut self) -> Token {
+        self.tokens.last().cloned().unwrap_or(Token {
+            kind: TokenKind::Eof,
+            loc: Location::default(),
+        })
+    }
+}
+
+/// Look at the next char in the iterator.
+fn lookahead(iter: &std::str::Chars<'_>) -> Option<char> {
+    let s = iter.as_str();
+    s.chars().next()
+}
+
+/// Track the location of the Token inside the string.
+#[derive(Copy, Clone, Default, Debug)]
+pub(crate) struct Location {
+    pub(crate) column: usize,
+}
+
+impl Location {
+    fn advance(&mut self, text: &str) {
+        self.column += text.chars().count();
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::OdbcString;
+
+    fn assert_kv(odbc: &OdbcString, key: &str, value: &str) {
+        assert_eq!(odbc.get(&key.to_lowercase()), Some(&value.to_owned()));
+    }
+
+    #[test]
+    fn sql_server_driver() -> crate::Result<()> {
+        let input = r#"Driver={ODBC Driver 18 for SQL Server};Server=tcp:host,1433;Pwd={p}}w;d}"#;
+        let odbc: OdbcString = input.parse()?;
+        assert_kv(&odbc, "Driver", "ODBC Driver 18 for SQL Server");
+        assert_kv(&odbc, "Server", "tcp:host,1433");
+        assert_kv(&odbc, "Pwd", "p}w;d");
+        Ok(())
+    }
+
+    #[test]
+    fn keys_are_case_insensitive() -> crate::Result<()> {
+        let input = "DRIVER=SQL Server;UID=sa;PWD=secret";
+        let odbc: OdbcString = input.parse()?;
+        assert_kv(&odbc, "driver", "SQL Server");
+        assert_kv(&odbc, "uid", "sa");
+        Ok(())
+    }
+
+    #[test]
+    fn display_with_escaping() -> crate::Result<()> {
+        let input = "key={val}}ue}";
+        let conn: OdbcString = input.parse()?;
+
+        assert_eq!(format!("{}", conn), input);
+        Ok(())
+    }
+
+    #[test]
+    fn display_preserves_multi_key_order() -> crate::Result<()> {
+        let input = "driver=A;server=B;uid=C;pwd=D";
+        let conn: OdbcString = input.parse()?;
+
+        assert_eq!(format!("{}", conn), input);
+        Ok(())
+    }
+}
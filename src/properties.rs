@@ -0,0 +1,152 @@
+use std::iter::FromIterator;
+
+/// An order-preserving multimap of connection-string properties.
+///
+/// A key may be recorded more than once: [`PropertyMap::append`] keeps every
+/// value, while [`PropertyMap::insert`] replaces the most recently inserted
+/// value for a key in place (mirroring `HashMap::insert`'s semantics for
+/// callers that don't care about duplicates). Iteration, and therefore
+/// `Display`, always follows insertion order, so a connection string that
+/// legitimately repeats a key round-trips losslessly.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PropertyMap {
+    entries: Vec<(String, String)>,
+}
+
+impl PropertyMap {
+    /// Create an empty `PropertyMap`.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Insert a value for `key`, replacing the most recently inserted value
+    /// for that key, if any, and returning it.
+    pub fn insert(&mut self, key: String, value: String) -> Option<String> {
+        match self.entries.iter_mut().rev().find(|(k, _)| *k == key) {
+            Some((_, existing)) => Some(std::mem::replace(existing, value)),
+            None => {
+                self.entries.push((key, value));
+                None
+            }
+        }
+    }
+
+    /// Append a value for `key`, keeping any value(s) already recorded for it
+    /// rather than replacing them.
+    pub fn append(&mut self, key: String, value: String) {
+        self.entries.push((key, value));
+    }
+
+    /// The most recently inserted value for `key`, if any.
+    pub fn get(&self, key: &str) -> Option<&String> {
+        self.entries
+            .iter()
+            .rev()
+            .find(|(k, _)| k == key)
+            .map(|(_, v)| v)
+    }
+
+    /// All values recorded for `key`, in insertion order.
+    pub fn get_all<'a>(&'a self, key: &'a str) -> impl Iterator<Item = &'a String> + 'a {
+        self.entries
+            .iter()
+            .filter(move |(k, _)| k == key)
+            .map(|(_, v)| v)
+    }
+
+    /// Iterate over all key-value pairs in insertion order.
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.entries.iter().map(|(k, v)| (k.as_str(), v.as_str()))
+    }
+
+    /// Remove every value recorded for `key`, returning the most recently
+    /// inserted one, if any.
+    pub fn remove(&mut self, key: &str) -> Option<String> {
+        let mut removed = None;
+        self.entries.retain(|(k, v)| {
+            if k == key {
+                removed = Some(v.clone());
+                false
+            } else {
+                true
+            }
+        });
+        removed
+    }
+
+    /// The number of entries, counting a repeated key once per occurrence.
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Whether the map has no entries.
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+}
+
+impl FromIterator<(String, String)> for PropertyMap {
+    fn from_iter<I: IntoIterator<Item = (String, String)>>(iter: I) -> Self {
+        let mut map = Self::new();
+        for (k, v) in iter {
+            map.insert(k, v);
+        }
+        map
+    }
+}
+
+impl<'a> IntoIterator for &'a PropertyMap {
+    type Item = (&'a str, &'a str);
+    type IntoIter = Box<dyn Iterator<Item = (&'a str, &'a str)> + 'a>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        Box::new(self.iter())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::PropertyMap;
+
+    #[test]
+    fn insert_replaces_in_place() {
+        let mut map = PropertyMap::new();
+        map.insert("a".into(), "1".into());
+        map.insert("b".into(), "2".into());
+        let old = map.insert("a".into(), "3".into());
+
+        assert_eq!(old, Some("1".to_owned()));
+        assert_eq!(map.get("a"), Some(&"3".to_owned()));
+        assert_eq!(
+            map.iter().collect::<Vec<_>>(),
+            vec![("a", "3"), ("b", "2")]
+        );
+    }
+
+    #[test]
+    fn append_preserves_all_values_in_order() {
+        let mut map = PropertyMap::new();
+        map.append("key".into(), "one".into());
+        map.append("key".into(), "two".into());
+
+        assert_eq!(map.get("key"), Some(&"two".to_owned()));
+        assert_eq!(
+            map.get_all("key").collect::<Vec<_>>(),
+            vec![&"one".to_owned(), &"two".to_owned()]
+        );
+    }
+
+    #[test]
+    fn remove_drops_every_value_for_a_key() {
+        let mut map = PropertyMap::new();
+        map.append("key".into(), "one".into());
+        map.append("other".into(), "x".into());
+        map.append("key".into(), "two".into());
+
+        let removed = map.remove("key");
+
+        assert_eq!(removed, Some("two".to_owned()));
+        assert_eq!(map.get("key"), None);
+        assert_eq!(map.iter().collect::<Vec<_>>(), vec![("other", "x")]);
+    }
+}
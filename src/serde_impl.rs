@@ -0,0 +1,118 @@
+//! Optional [`serde`](https://docs.rs/serde) support, enabled with the
+//! `serde` feature.
+//!
+//! [`JdbcString`] and [`AdoNetString`] serialize to, and deserialize from, a
+//! single string using their existing `Display`/`FromStr` impls. That lets a
+//! config struct hold a connection string as a plain scalar field, the way a
+//! config-driven service would hydrate it from TOML/JSON/YAML, instead of as
+//! a nested object:
+//!
+//! ```
+//! # #[cfg(feature = "serde")] {
+//! use connection_string::JdbcString;
+//!
+//! #[derive(serde::Serialize, serde::Deserialize)]
+//! struct Config {
+//!     database_url: JdbcString,
+//! }
+//! # }
+//! ```
+//!
+//! Foreign types that can't implement `Serialize`/`Deserialize` here can opt
+//! into the same scalar representation with
+//! `#[serde(with = "connection_string::serde_as_string")]`.
+
+use std::fmt::Display;
+use std::str::FromStr;
+
+use serde::de::Error as _;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::{AdoNetString, JdbcString};
+
+impl Serialize for JdbcString {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for JdbcString {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        String::deserialize(deserializer)?
+            .parse()
+            .map_err(D::Error::custom)
+    }
+}
+
+impl Serialize for AdoNetString {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for AdoNetString {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        String::deserialize(deserializer)?
+            .parse()
+            .map_err(D::Error::custom)
+    }
+}
+
+/// `serialize_with`/`deserialize_with` helpers for `#[serde(with = "...")]`,
+/// for connection-string types that live outside this crate and so can't
+/// implement `Serialize`/`Deserialize` directly.
+pub mod serde_as_string {
+    use super::*;
+
+    /// Serialize any `Display` value as a string.
+    pub fn serialize<T, S>(value: &T, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        T: Display,
+        S: Serializer,
+    {
+        serializer.collect_str(value)
+    }
+
+    /// Deserialize any `FromStr` value from a string.
+    pub fn deserialize<'de, T, D>(deserializer: D) -> Result<T, D::Error>
+    where
+        T: FromStr,
+        T::Err: Display,
+        D: Deserializer<'de>,
+    {
+        String::deserialize(deserializer)?
+            .parse()
+            .map_err(D::Error::custom)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{AdoNetString, JdbcString};
+
+    #[test]
+    fn jdbc_string_round_trips_through_json() {
+        let input = r#"jdbc:sqlserver://server\instance:80;key=value"#;
+        let conn: JdbcString = input.parse().unwrap();
+
+        let json = serde_json::to_string(&conn).unwrap();
+        assert_eq!(json, format!("{:?}", input));
+
+        let roundtripped: JdbcString = serde_json::from_str(&json).unwrap();
+        assert_eq!(roundtripped, conn);
+    }
+
+    #[test]
+    fn ado_net_string_round_trips_through_json() {
+        let input = "Server=MSSQL1;Initial Catalog=AdventureWorks";
+        let ado: AdoNetString = input.parse().unwrap();
+
+        let json = serde_json::to_string(&ado).unwrap();
+        let roundtripped: AdoNetString = serde_json::from_str(&json).unwrap();
+        assert_eq!(roundtripped.get("server"), ado.get("server"));
+        assert_eq!(
+            roundtripped.get("initial catalog"),
+            ado.get("initial catalog")
+        );
+    }
+}
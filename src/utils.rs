@@ -2,15 +2,21 @@
 #[doc(hidden)]
 #[macro_export]
 macro_rules! ensure {
-    ($cond:expr, $msg:literal) => {
+    ($cond:expr, $kind:expr, $msg:literal) => {
         if !$cond {
-            return Err($crate::Error::new($msg.into()));
+            return Err($crate::Error::new($kind, $msg));
         };
     };
 
-    ($cond:expr, $msg:expr) => {
+    ($cond:expr, $kind:expr, $msg:expr) => {
         if !$cond {
-            return Err($crate::Error::new($msg.into()));
+            return Err($crate::Error::new($kind, $msg));
+        };
+    };
+
+    ($cond:expr, $kind:expr, $msg:expr, $loc:expr) => {
+        if !$cond {
+            return Err($crate::Error::with_column($kind, $msg, $loc.column));
         };
     };
 }
@@ -19,15 +25,23 @@ macro_rules! ensure {
 #[doc(hidden)]
 #[macro_export]
 macro_rules! bail {
-    ($msg:literal) => {
-        return Err($crate::Error::new($msg.into()));
+    ($kind:expr, $msg:literal, @$loc:expr) => {
+        return Err($crate::Error::with_column($kind, $msg, $loc.column));
+    };
+
+    ($kind:expr, $msg:expr, @$loc:expr) => {
+        return Err($crate::Error::with_column($kind, $msg, $loc.column));
+    };
+
+    ($kind:expr, $msg:literal) => {
+        return Err($crate::Error::new($kind, $msg));
     };
 
-    ($msg:expr) => {
-        return Err($crate::Error::new($msg.into()));
+    ($kind:expr, $msg:expr) => {
+        return Err($crate::Error::new($kind, $msg));
     };
 
-    ($fmt:expr, $($arg:tt)*) => {
-        return Err($crate::Error::new(&*format!($fmt, $($arg)*)));
+    ($kind:expr, $fmt:expr, $($arg:tt)*) => {
+        return Err($crate::Error::new($kind, &*format!($fmt, $($arg)*)));
     };
 }
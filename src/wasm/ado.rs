@@ -1,5 +1,8 @@
+use std::convert::TryFrom;
 use wasm_bindgen::prelude::*;
 
+use crate::ConnectionInfo;
+
 #[wasm_bindgen]
 #[derive(Debug)]
 /// A version of `JdbcString` to be used from web-assembly.
@@ -35,4 +38,12 @@ impl AdoNetString {
     pub fn to_string(&self) -> String {
         format!("{}", self.inner)
     }
+
+    /// Convert this connection string to an equivalent JDBC connection string.
+    pub fn to_jdbc(&self) -> Result<String, JsValue> {
+        let info = ConnectionInfo::try_from(&self.inner)
+            .map_err(|err| JsValue::from_str(&format!("{}", err)))?;
+
+        Ok(format!("{}", info.to_jdbc()))
+    }
 }